@@ -0,0 +1,104 @@
+use crate::tokenizer::Result;
+
+use super::kind::TokenKind;
+
+/// A single lexed span within a code block, expressed as byte offsets
+/// relative to the start of the whole normalized string (i.e. already
+/// shifted by the `offset` passed to [`CodeLexerBackend::lex`]), together
+/// with an optional lexical classification (`None` when a backend can't
+/// or doesn't classify its tokens).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LexedSpan {
+    pub start: usize,
+    pub end: usize,
+    pub kind: Option<TokenKind>,
+}
+
+impl LexedSpan {
+    pub fn new(start: usize, end: usize, kind: Option<TokenKind>) -> Self {
+        Self { start, end, kind }
+    }
+
+    pub fn unclassified(start: usize, end: usize) -> Self {
+        Self::new(start, end, None)
+    }
+}
+
+impl From<(usize, usize)> for LexedSpan {
+    fn from((start, end): (usize, usize)) -> Self {
+        Self::unclassified(start, end)
+    }
+}
+
+/// Options threaded down to every backend, controlling lexing behavior
+/// that isn't specific to any one language.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LexOptions {
+    /// When `false` (the default), comment and string-literal spans are
+    /// kept as single atomic spans, matching how most tokenizer vocabularies
+    /// want to treat them. When `true`, backends that support it will
+    /// sub-lex the interior of comments/strings (e.g. on whitespace) instead
+    /// of emitting one opaque span, which is useful for lexical-category
+    /// aware segmentation that still wants sub-word granularity inside them.
+    pub sub_lex_comments_and_strings: bool,
+}
+
+/// A backend capable of turning a code string into a sequence of
+/// non-overlapping, ordered byte spans.
+///
+/// Implementors should not assume anything about the surrounding text:
+/// `code` is exactly the contents of the fenced block (no fence markers),
+/// and `offset` is where `code` begins within the original string, so
+/// spans returned here can be plugged directly into `Range::Original`.
+pub trait CodeLexerBackend: std::fmt::Debug {
+    /// Lex `code` and return the resulting spans, already shifted by `offset`.
+    fn lex(&self, code: &str, offset: usize, options: &LexOptions) -> Result<Vec<LexedSpan>>;
+}
+
+/// Registry mapping language identifiers (lower-cased) to the backend that
+/// should lex them.
+///
+/// This is what lets [`super::CodeLexer`] stay agnostic of any particular
+/// language: adding support for a new language is a matter of registering
+/// another `(identifier, backend)` pair, not touching `pre_tokenize`.
+#[derive(Debug, Clone)]
+pub struct BackendRegistry {
+    backends: Vec<(String, std::sync::Arc<dyn CodeLexerBackend + Send + Sync>)>,
+    fallback: std::sync::Arc<dyn CodeLexerBackend + Send + Sync>,
+}
+
+impl BackendRegistry {
+    pub fn new(fallback: std::sync::Arc<dyn CodeLexerBackend + Send + Sync>) -> Self {
+        Self {
+            backends: Vec::new(),
+            fallback,
+        }
+    }
+
+    /// Register `backend` to handle `language` (case-insensitive), replacing
+    /// any backend previously registered for the same language (including
+    /// one of the built-ins) so the last registration wins.
+    pub fn register(
+        &mut self,
+        language: impl Into<String>,
+        backend: std::sync::Arc<dyn CodeLexerBackend + Send + Sync>,
+    ) {
+        let language = language.into().to_lowercase();
+        if let Some(slot) = self.backends.iter_mut().find(|(lang, _)| *lang == language) {
+            slot.1 = backend;
+        } else {
+            self.backends.push((language, backend));
+        }
+    }
+
+    /// Look up the backend registered for `language`, falling back to the
+    /// backend given to [`BackendRegistry::new`] when none matches.
+    pub fn get(&self, language: &str) -> &(dyn CodeLexerBackend + Send + Sync) {
+        let language = language.to_lowercase();
+        self.backends
+            .iter()
+            .find(|(lang, _)| lang == &language)
+            .map(|(_, backend)| backend.as_ref())
+            .unwrap_or(self.fallback.as_ref())
+    }
+}
@@ -0,0 +1,61 @@
+use super::backend::{CodeLexerBackend, LexOptions, LexedSpan};
+use super::kind::TokenKind;
+use crate::tokenizer::Result;
+
+/// Generic fallback backend used for languages that don't have a dedicated
+/// lexer registered. Splits on whitespace boundaries, grouping each run of
+/// non-whitespace characters into its own span so that, at minimum, code in
+/// an unsupported language doesn't collapse into a single giant token.
+#[derive(Debug, Clone, Default)]
+pub struct WhitespaceWordBackend;
+
+/// Best-effort classification for a whitespace-delimited word: since this
+/// backend doesn't know the language's grammar, it can only distinguish
+/// numbers and identifier-like words from everything else.
+fn classify(word: &str) -> TokenKind {
+    if word.chars().any(|c| c.is_ascii_digit())
+        && word.chars().all(|c| c.is_ascii_digit() || c == '.')
+    {
+        TokenKind::Number
+    } else if word
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '_')
+    {
+        TokenKind::Identifier
+    } else {
+        TokenKind::Other
+    }
+}
+
+impl CodeLexerBackend for WhitespaceWordBackend {
+    fn lex(&self, code: &str, offset: usize, _options: &LexOptions) -> Result<Vec<LexedSpan>> {
+        let mut spans = Vec::new();
+        let mut word_start: Option<usize> = None;
+
+        for (i, c) in code.char_indices() {
+            if c.is_whitespace() {
+                if let Some(start) = word_start.take() {
+                    let word = &code[start..i];
+                    spans.push(LexedSpan::new(offset + start, offset + i, Some(classify(word))));
+                }
+            } else if word_start.is_none() {
+                word_start = Some(i);
+            }
+        }
+
+        if let Some(start) = word_start {
+            let word = &code[start..];
+            spans.push(LexedSpan::new(
+                offset + start,
+                offset + code.len(),
+                Some(classify(word)),
+            ));
+        }
+
+        if spans.is_empty() {
+            spans.push(LexedSpan::unclassified(offset, offset + code.len()));
+        }
+
+        Ok(spans)
+    }
+}
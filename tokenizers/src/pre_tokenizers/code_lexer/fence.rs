@@ -0,0 +1,229 @@
+//! CommonMark-style fenced code block detection.
+//!
+//! This intentionally only implements the subset of the CommonMark fenced
+//! code block spec that matters for pre-tokenization: finding where a block
+//! starts and ends and what language (if any) it declares. It does not
+//! strip per-line indentation from fenced content, since the rest of the
+//! crate works with original-string byte offsets and wants them untouched.
+
+/// A single fenced code block found in some text.
+#[derive(Debug, PartialEq, Eq)]
+pub struct FencedBlock<'a> {
+    /// Start of the opening fence line (including its indentation, if any).
+    pub header_start: usize,
+    /// End of the opening fence line, including its trailing newline.
+    pub header_end: usize,
+    /// Start of the fenced content (== `header_end`).
+    pub content_start: usize,
+    /// End of the fenced content (== start of the closing fence line).
+    pub content_end: usize,
+    /// End of the closing fence line, including its trailing newline (or
+    /// end of input, if the closing fence is the last line).
+    pub footer_end: usize,
+    /// The first word of the info string (e.g. `python` in ` ```python
+    /// title="x.py" `), if any. The rest of the info string is not parsed
+    /// further - it's simply left alone as part of the header line.
+    pub lang: Option<&'a str>,
+}
+
+/// `(line_start, content_end, line_end)`: `content_end` excludes a trailing
+/// `\n`, `line_end` includes it (or equals `content_end` for the final
+/// line if the text doesn't end with a newline).
+fn line_spans(text: &str) -> Vec<(usize, usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = 0usize;
+    let bytes = text.as_bytes();
+
+    for i in 0..bytes.len() {
+        if bytes[i] == b'\n' {
+            spans.push((start, i, i + 1));
+            start = i + 1;
+        }
+    }
+    if start < text.len() || text.is_empty() {
+        spans.push((start, text.len(), text.len()));
+    }
+
+    spans
+}
+
+const MAX_FENCE_INDENT: usize = 3;
+
+/// If `line` (given as `(start, content_end)` into `text`) opens a fence,
+/// return `(fence_start, fence_char, fence_len)` - where `fence_start` is
+/// right after the (up to 3 spaces of) indentation.
+fn parse_fence_open(text: &str, start: usize, content_end: usize) -> Option<(usize, char, usize)> {
+    let line = &text[start..content_end];
+    let indent = line.chars().take_while(|c| *c == ' ').count();
+    if indent > MAX_FENCE_INDENT {
+        return None;
+    }
+
+    let rest = &line[indent..];
+    let fence_char = rest.chars().next()?;
+    if fence_char != '`' && fence_char != '~' {
+        return None;
+    }
+
+    let fence_len = rest.chars().take_while(|c| *c == fence_char).count();
+    if fence_len < 3 {
+        return None;
+    }
+
+    // Per CommonMark, a backtick-fenced info string can't itself contain a
+    // backtick (it would be ambiguous with inline code spans).
+    let info = &rest[fence_len..];
+    if fence_char == '`' && info.contains('`') {
+        return None;
+    }
+
+    Some((start + indent, fence_char, fence_len))
+}
+
+/// Whether `line` (given as `(start, content_end)` into `text`) closes a
+/// fence opened with `fence_char` repeated `fence_len` times: up to 3
+/// spaces of indentation, a run of at least `fence_len` of the same
+/// character, and nothing but whitespace after it.
+fn is_fence_close(text: &str, start: usize, content_end: usize, fence_char: char, fence_len: usize) -> bool {
+    let line = &text[start..content_end];
+    let indent = line.chars().take_while(|c| *c == ' ').count();
+    if indent > MAX_FENCE_INDENT {
+        return false;
+    }
+
+    let rest = &line[indent..];
+    let run_len = rest.chars().take_while(|c| *c == fence_char).count();
+    if run_len < fence_len {
+        return false;
+    }
+
+    rest[run_len..].chars().all(char::is_whitespace)
+}
+
+/// Extract the first word of an info string (the language tag), if any.
+fn parse_lang(info: &str) -> Option<&str> {
+    let trimmed = info.trim_start();
+    let lang_len = trimmed
+        .find(char::is_whitespace)
+        .unwrap_or(trimmed.len());
+    if lang_len == 0 {
+        None
+    } else {
+        Some(&trimmed[..lang_len])
+    }
+}
+
+/// Scan `text` for CommonMark-style fenced code blocks: backtick or tilde
+/// fences, any fence length >= 3, indented by up to 3 spaces, with a
+/// closing fence of at least the opening length and the same character.
+pub fn find_fenced_code_blocks(text: &str) -> Vec<FencedBlock<'_>> {
+    let lines = line_spans(text);
+    let mut blocks = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let (start, content_end, line_end) = lines[i];
+
+        if let Some((fence_start, fence_char, fence_len)) = parse_fence_open(text, start, content_end) {
+            let info = &text[fence_start + fence_len..content_end];
+
+            let close_idx = ((i + 1)..lines.len()).find(|&j| {
+                let (s, ce, _) = lines[j];
+                is_fence_close(text, s, ce, fence_char, fence_len)
+            });
+
+            if let Some(close_idx) = close_idx {
+                let (close_start, _, close_line_end) = lines[close_idx];
+                blocks.push(FencedBlock {
+                    header_start: start,
+                    header_end: line_end,
+                    content_start: line_end,
+                    content_end: close_start,
+                    footer_end: close_line_end,
+                    lang: parse_lang(info),
+                });
+                i = close_idx + 1;
+                continue;
+            }
+        }
+
+        i += 1;
+    }
+
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backtick_fence_with_language() {
+        let text = "```python\nx = 1\n```";
+        let blocks = find_fenced_code_blocks(text);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].lang, Some("python"));
+        assert_eq!(&text[blocks[0].content_start..blocks[0].content_end], "x = 1\n");
+    }
+
+    #[test]
+    fn tilde_fence() {
+        let text = "~~~rust\nfn main() {}\n~~~";
+        let blocks = find_fenced_code_blocks(text);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].lang, Some("rust"));
+    }
+
+    #[test]
+    fn longer_closing_fence_is_accepted() {
+        let text = "```python\nx = 1\n``````";
+        let blocks = find_fenced_code_blocks(text);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(&text[blocks[0].content_start..blocks[0].content_end], "x = 1\n");
+    }
+
+    #[test]
+    fn shorter_closing_fence_does_not_close() {
+        // A 4-backtick fence can't be closed by 3 backticks.
+        let text = "````python\nx = 1\n```\nstill inside\n````";
+        let blocks = find_fenced_code_blocks(text);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(
+            &text[blocks[0].content_start..blocks[0].content_end],
+            "x = 1\n```\nstill inside\n"
+        );
+    }
+
+    #[test]
+    fn indented_fence() {
+        let text = "  ```python\n  x = 1\n  ```";
+        let blocks = find_fenced_code_blocks(text);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].header_start, 0);
+    }
+
+    #[test]
+    fn info_string_attributes_pass_through_untouched() {
+        let text = "```python title=\"x.py\"\nx = 1\n```";
+        let blocks = find_fenced_code_blocks(text);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].lang, Some("python"));
+    }
+
+    #[test]
+    fn unterminated_fence_is_not_a_block() {
+        let text = "```python\nx = 1";
+        assert!(find_fenced_code_blocks(text).is_empty());
+    }
+
+    #[test]
+    fn mismatched_fence_characters_do_not_close() {
+        let text = "```python\nx = 1\n~~~\n```";
+        let blocks = find_fenced_code_blocks(text);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(
+            &text[blocks[0].content_start..blocks[0].content_end],
+            "x = 1\n~~~\n"
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use super::backend::LexedSpan;
+use super::kind::TokenKind;
+
+/// Options controlling [`split_identifier_spans`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IdentifierSplitOptions {
+    /// Also split at letter<->digit transitions (e.g. `max2count` -> `max`, `2`, `count`).
+    pub split_on_digit_transitions: bool,
+    /// Keep the original, whole-identifier span in addition to the subword
+    /// spans it was split into.
+    pub keep_whole_identifier: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Upper,
+    Lower,
+    Digit,
+    Sep,
+    Other,
+}
+
+fn classify(c: char) -> CharClass {
+    if c == '_' || c == '-' {
+        CharClass::Sep
+    } else if c.is_uppercase() {
+        CharClass::Upper
+    } else if c.is_lowercase() {
+        CharClass::Lower
+    } else if c.is_ascii_digit() {
+        CharClass::Digit
+    } else {
+        CharClass::Other
+    }
+}
+
+/// Find the byte offsets (relative to `word`) where a new subword starts.
+/// Always includes `0`. Separators (`_`, `-`) start a new subword (so they
+/// stay attached to the word that follows them, keeping the split lossless
+/// and contiguous); camelCase humps and, optionally, digit<->letter
+/// transitions also start a new subword.
+fn find_boundaries(word: &str, split_on_digit_transitions: bool) -> Vec<usize> {
+    let chars: Vec<(usize, char)> = word.char_indices().collect();
+    if chars.is_empty() {
+        return vec![0];
+    }
+
+    let mut boundaries = vec![0usize];
+
+    for i in 1..chars.len() {
+        let prev_class = classify(chars[i - 1].1);
+        let cur_class = classify(chars[i].1);
+
+        let boundary = if cur_class == CharClass::Sep && prev_class != CharClass::Sep {
+            // Start of a new separator run: attach it to the following subword.
+            true
+        } else if prev_class == CharClass::Lower && cur_class == CharClass::Upper {
+            // camelCase hump: "getUser" -> "get", "User"
+            true
+        } else if prev_class == CharClass::Upper && cur_class == CharClass::Upper {
+            // Acronym boundary: "HTTPServer" -> "HTTP", "Server" (split
+            // before the capital that starts a new word, i.e. the last one
+            // of the run when followed by a lowercase letter).
+            chars
+                .get(i + 1)
+                .map(|&(_, next_c)| classify(next_c) == CharClass::Lower)
+                .unwrap_or(false)
+        } else {
+            split_on_digit_transitions
+                && (prev_class == CharClass::Digit) != (cur_class == CharClass::Digit)
+                && prev_class != CharClass::Sep
+                && cur_class != CharClass::Sep
+                && prev_class != CharClass::Other
+                && cur_class != CharClass::Other
+        };
+
+        if boundary {
+            boundaries.push(chars[i].0);
+        }
+    }
+
+    boundaries
+}
+
+/// Post-lex filter that splits spans classified as [`TokenKind::Identifier`]
+/// into subwords on camelCase humps and `snake_case`/`kebab-case`
+/// separators (and, optionally, digit<->letter transitions), analogous to
+/// the compound-word splitting filters found in full-text tokenizer
+/// pipelines. Non-identifier spans pass through unchanged.
+pub fn split_identifier_spans(
+    text: &str,
+    spans: Vec<LexedSpan>,
+    options: &IdentifierSplitOptions,
+) -> Vec<LexedSpan> {
+    let mut result = Vec::with_capacity(spans.len());
+
+    for span in spans {
+        if span.kind != Some(TokenKind::Identifier) {
+            result.push(span);
+            continue;
+        }
+
+        let word = &text[span.start..span.end];
+        let boundaries = find_boundaries(word, options.split_on_digit_transitions);
+
+        if boundaries.len() <= 1 {
+            result.push(span);
+            continue;
+        }
+
+        if options.keep_whole_identifier {
+            result.push(span);
+        }
+
+        for (i, &start) in boundaries.iter().enumerate() {
+            let end = boundaries.get(i + 1).copied().unwrap_or(word.len());
+            result.push(LexedSpan::new(
+                span.start + start,
+                span.start + end,
+                Some(TokenKind::Identifier),
+            ));
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(word: &str, split_digits: bool) -> Vec<&str> {
+        let spans = split_identifier_spans(
+            word,
+            vec![LexedSpan::new(0, word.len(), Some(TokenKind::Identifier))],
+            &IdentifierSplitOptions {
+                split_on_digit_transitions: split_digits,
+                keep_whole_identifier: false,
+            },
+        );
+        spans.into_iter().map(|s| &word[s.start..s.end]).collect()
+    }
+
+    #[test]
+    fn splits_camel_case() {
+        assert_eq!(split("getUserName", false), vec!["get", "User", "Name"]);
+    }
+
+    #[test]
+    fn splits_acronym_followed_by_word() {
+        assert_eq!(split("HTTPServer", false), vec!["HTTP", "Server"]);
+    }
+
+    #[test]
+    fn splits_snake_and_kebab_case() {
+        assert_eq!(split("max_retry_count", false), vec!["max", "_retry", "_count"]);
+        assert_eq!(split("max-retry-count", false), vec!["max", "-retry", "-count"]);
+    }
+
+    #[test]
+    fn leaves_plain_identifiers_untouched() {
+        assert_eq!(split("identifier", false), vec!["identifier"]);
+    }
+
+    #[test]
+    fn digit_transitions_are_opt_in() {
+        assert_eq!(split("max2count", false), vec!["max2count"]);
+        assert_eq!(split("max2count", true), vec!["max", "2", "count"]);
+    }
+
+    #[test]
+    fn keep_whole_identifier_preserves_original_span_too() {
+        let word = "getUserName";
+        let spans = split_identifier_spans(
+            word,
+            vec![LexedSpan::new(0, word.len(), Some(TokenKind::Identifier))],
+            &IdentifierSplitOptions {
+                split_on_digit_transitions: false,
+                keep_whole_identifier: true,
+            },
+        );
+        let rendered: Vec<&str> = spans.iter().map(|s| &word[s.start..s.end]).collect();
+        assert_eq!(rendered, vec!["getUserName", "get", "User", "Name"]);
+    }
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// Lexical category of a code span, following the `Token { kind, len }`
+/// model used by rust-analyzer's lexer: a coarse classification that's
+/// enough to distinguish keywords from identifiers from literals without
+/// trying to capture every language's full token set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenKind {
+    Keyword,
+    Identifier,
+    StringLiteral,
+    Number,
+    Operator,
+    Comment,
+    Whitespace,
+    /// Anything a backend can't otherwise classify (punctuation, unknown
+    /// characters, etc).
+    Other,
+}
@@ -0,0 +1,581 @@
+mod backend;
+mod fallback;
+mod fence;
+mod identifier_split;
+mod kind;
+mod python;
+mod regex_lexer;
+mod rust_lexer;
+
+pub use backend::{BackendRegistry, CodeLexerBackend, LexOptions, LexedSpan};
+pub use fallback::WhitespaceWordBackend;
+pub use fence::{find_fenced_code_blocks, FencedBlock};
+pub use identifier_split::IdentifierSplitOptions;
+pub use kind::TokenKind;
+pub use python::PythonBackend;
+pub use regex_lexer::{Group, RegexLexer, Rule};
+pub use rust_lexer::RustBackend;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::tokenizer::normalizer::Range;
+use crate::tokenizer::{PreTokenizedString, PreTokenizer, Result};
+
+/// CodeLexer pre-tokenizer that applies language-specific lexing to code blocks.
+///
+/// This pre-tokenizer:
+/// 1. Finds CommonMark-style fenced code blocks (backtick or tilde fences,
+///    any fence length >= 3, indented by up to 3 spaces, with an info
+///    string whose first word names the language)
+/// 2. Applies a language-specific [`CodeLexerBackend`] to extract tokens
+/// 3. Leaves non-code text unchanged
+///
+/// Backends are looked up through a [`BackendRegistry`] keyed by language
+/// identifier, so supporting a new language is a matter of registering
+/// another backend rather than editing `pre_tokenize` itself. Languages
+/// that are listed in `languages` but have no dedicated backend fall back
+/// to [`WhitespaceWordBackend`] instead of being left untouched. Additional
+/// languages can be covered without writing a native backend at all by
+/// registering a [`RegexLexer`] (a data-driven, group/state-machine lexer)
+/// through [`CodeLexer::register_regex_backend`] - unlike [`CodeLexer::register_backend`],
+/// these are stored on `CodeLexer` itself and serialize with it, so a
+/// language covered purely by a JSON lexer definition survives a
+/// save/reload round-trip without any Rust code running again.
+///
+/// Backends that support it (currently [`PythonBackend`]) recover from
+/// lexical errors instead of giving up on the whole block: good boundaries
+/// before the error are kept, the malformed region becomes one fallback
+/// span up to the next resynchronization point, and lexing resumes after
+/// it. This gives graceful degradation on real-world code blocks that
+/// happen to contain a syntax error.
+///
+/// When `split_identifiers` is enabled, spans classified as
+/// [`TokenKind::Identifier`] are additionally split into subwords on
+/// camelCase humps and `snake_case`/`kebab-case` separators (see
+/// [`identifier_split::split_identifier_spans`]), which tends to produce
+/// better-shared vocabulary when training tokenizers on source code.
+///
+/// Each produced span also carries an optional [`TokenKind`] classification
+/// (keyword, identifier, string literal, ...). Since splits themselves don't
+/// carry arbitrary metadata, [`CodeLexer::token_kinds`] recomputes and
+/// returns it for a given input string on demand, for consumers that want
+/// lexical-category-aware segmentation. It's a pure function of `self` and
+/// the text passed in (no state is cached on `CodeLexer`), so it's safe to
+/// call concurrently from multiple threads on a shared `CodeLexer`, as
+/// `encode_batch` does.
+#[derive(Debug, Serialize, Clone)]
+#[serde(tag = "type")]
+pub struct CodeLexer {
+    /// List of language identifiers to apply lexing to (e.g., ["python", "py"])
+    pub languages: Vec<String>,
+    /// Whether comments/string literals should be sub-lexed instead of kept
+    /// as a single atomic span. Defaults to `false` (atomic).
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub sub_lex_comments_and_strings: bool,
+    /// Whether identifier spans (e.g. `getUserName`, `max_retry_count`) are
+    /// split into subwords on camelCase humps and `snake_case`/`kebab-case`
+    /// separators. Defaults to `false`.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub split_identifiers: bool,
+    /// Also split identifier subwords at letter<->digit transitions. Only
+    /// has an effect when `split_identifiers` is enabled. Defaults to `false`.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub split_identifier_digits: bool,
+    /// Keep the original, whole-identifier span alongside the subwords it
+    /// was split into. Only has an effect when `split_identifiers` is
+    /// enabled. Defaults to `false`.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub keep_whole_identifier_span: bool,
+    /// Data-driven [`RegexLexer`] backends registered through
+    /// [`CodeLexer::register_regex_backend`], keyed by (lower-cased)
+    /// language identifier. Unlike backends registered through
+    /// [`CodeLexer::register_backend`], these are plain data and are part of
+    /// `CodeLexer`'s own serialized state.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub custom_lexers: HashMap<String, RegexLexer>,
+    #[serde(skip)]
+    registry: BackendRegistry,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+impl<'de> Deserialize<'de> for CodeLexer {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        enum Type {
+            CodeLexer,
+        }
+
+        #[derive(Deserialize)]
+        pub struct CodeLexerHelper {
+            #[serde(rename = "type")]
+            _type: Type,
+            #[serde(default = "default_languages")]
+            languages: Vec<String>,
+            #[serde(default)]
+            sub_lex_comments_and_strings: bool,
+            #[serde(default)]
+            split_identifiers: bool,
+            #[serde(default)]
+            split_identifier_digits: bool,
+            #[serde(default)]
+            keep_whole_identifier_span: bool,
+            #[serde(default)]
+            custom_lexers: HashMap<String, RegexLexer>,
+        }
+
+        let helper = CodeLexerHelper::deserialize(deserializer)?;
+        let mut lexer = Self::new(helper.languages)
+            .with_sub_lex_comments_and_strings(helper.sub_lex_comments_and_strings)
+            .with_identifier_splitting(helper.split_identifiers)
+            .with_split_identifier_digits(helper.split_identifier_digits)
+            .with_keep_whole_identifier_span(helper.keep_whole_identifier_span);
+        for (language, regex_lexer) in helper.custom_lexers {
+            lexer.register_regex_backend(language, regex_lexer);
+        }
+        Ok(lexer)
+    }
+}
+
+fn default_languages() -> Vec<String> {
+    vec!["python".to_string(), "py".to_string()]
+}
+
+/// Builds the registry mapping each known language identifier to the
+/// backend that should lex it. Identifiers not listed here still get
+/// lexed (via [`WhitespaceWordBackend`]) as long as they appear in
+/// `languages`.
+fn default_registry() -> BackendRegistry {
+    let mut registry = BackendRegistry::new(Arc::new(WhitespaceWordBackend));
+
+    let python_backend = Arc::new(PythonBackend);
+    registry.register("python", python_backend.clone());
+    registry.register("py", python_backend);
+
+    let rust_backend = Arc::new(RustBackend);
+    registry.register("rust", rust_backend.clone());
+    registry.register("rs", rust_backend);
+
+    registry
+}
+
+impl PartialEq for CodeLexer {
+    fn eq(&self, other: &Self) -> bool {
+        self.languages == other.languages
+            && self.sub_lex_comments_and_strings == other.sub_lex_comments_and_strings
+            && self.split_identifiers == other.split_identifiers
+            && self.split_identifier_digits == other.split_identifier_digits
+            && self.keep_whole_identifier_span == other.keep_whole_identifier_span
+            && self.custom_lexers == other.custom_lexers
+    }
+}
+
+impl CodeLexer {
+    pub fn new(languages: Vec<String>) -> Self {
+        Self {
+            languages,
+            sub_lex_comments_and_strings: false,
+            split_identifiers: false,
+            split_identifier_digits: false,
+            keep_whole_identifier_span: false,
+            custom_lexers: HashMap::new(),
+            registry: default_registry(),
+        }
+    }
+
+    pub fn default() -> Self {
+        Self::new(default_languages())
+    }
+
+    /// Control whether comments/string literals are sub-lexed instead of
+    /// kept as a single atomic span (see [`LexOptions`]).
+    pub fn with_sub_lex_comments_and_strings(mut self, sub_lex: bool) -> Self {
+        self.sub_lex_comments_and_strings = sub_lex;
+        self
+    }
+
+    /// Control whether identifier spans are split into camelCase/snake_case/
+    /// kebab-case subwords.
+    pub fn with_identifier_splitting(mut self, split: bool) -> Self {
+        self.split_identifiers = split;
+        self
+    }
+
+    /// Control whether identifier subword splitting also splits at
+    /// letter<->digit transitions.
+    pub fn with_split_identifier_digits(mut self, split_digits: bool) -> Self {
+        self.split_identifier_digits = split_digits;
+        self
+    }
+
+    /// Control whether identifier subword splitting keeps the original,
+    /// whole-identifier span alongside the subwords.
+    pub fn with_keep_whole_identifier_span(mut self, keep_whole: bool) -> Self {
+        self.keep_whole_identifier_span = keep_whole;
+        self
+    }
+
+    /// Register a custom backend for `language`, overriding whatever was
+    /// previously registered (including the built-in Python/Rust backends).
+    ///
+    /// This accepts any [`CodeLexerBackend`], including ones backed by
+    /// arbitrary Rust code, but such backends are *not* part of `CodeLexer`'s
+    /// serialized state (there is no way to serialize an arbitrary trait
+    /// object) - they must be re-registered after every deserialize. For a
+    /// backend that should survive a save/reload round-trip, use
+    /// [`CodeLexer::register_regex_backend`] instead.
+    pub fn register_backend(
+        &mut self,
+        language: impl Into<String>,
+        backend: Arc<dyn CodeLexerBackend + Send + Sync>,
+    ) {
+        self.registry.register(language, backend);
+    }
+
+    /// Register a data-driven [`RegexLexer`] for `language`, overriding
+    /// whatever was previously registered. Unlike [`CodeLexer::register_backend`],
+    /// the lexer definition itself is stored on `CodeLexer` and serializes
+    /// with it, so languages covered purely by a JSON lexer definition keep
+    /// working after a save/reload round-trip with no Rust calls required.
+    pub fn register_regex_backend(&mut self, language: impl Into<String>, lexer: RegexLexer) {
+        let language = language.into().to_lowercase();
+        self.registry.register(language.clone(), Arc::new(lexer.clone()));
+        self.custom_lexers.insert(language, lexer);
+    }
+
+    /// Check if a language is supported for lexing
+    fn is_supported_language(&self, lang: &str) -> bool {
+        self.languages.iter().any(|l| l.eq_ignore_ascii_case(lang))
+    }
+
+    /// Apply the registered lexer backend for `lang`.
+    fn lex_code(&self, lang: &str, code: &str, offset: usize) -> Result<Vec<LexedSpan>> {
+        let options = LexOptions {
+            sub_lex_comments_and_strings: self.sub_lex_comments_and_strings,
+        };
+        self.registry.get(lang).lex(code, offset, &options)
+    }
+
+    /// Token-kind metadata for the spans `text` would be split into, as
+    /// `(start, end, kind)` triples in `text`'s own byte offsets. Spans with
+    /// no classification are omitted.
+    ///
+    /// This recomputes lexing for `text` rather than reading back state from
+    /// a prior `pre_tokenize` call, so it's safe to call from any thread at
+    /// any time, including concurrently with `pre_tokenize` running on other
+    /// documents.
+    pub fn token_kinds(&self, text: &str) -> Vec<(usize, usize, TokenKind)> {
+        self.compute_splits(text).1
+    }
+
+    /// Compute the ordered `(start, end)` spans `text` should be split into,
+    /// plus `(start, end, kind)` triples for every span that carries a
+    /// [`TokenKind`]. Pure function of `self` and `text` - touches no shared
+    /// mutable state - so callers may invoke it concurrently on a shared
+    /// `CodeLexer`.
+    fn compute_splits(&self, text: &str) -> (Vec<(usize, usize)>, Vec<(usize, usize, TokenKind)>) {
+        let mut last_end = 0;
+        let mut splits = Vec::new();
+        let mut kinds = Vec::new();
+
+        for block in find_fenced_code_blocks(text) {
+            // Add text before the fence
+            if block.header_start > last_end {
+                splits.push((last_end, block.header_start));
+            }
+
+            // Add opening fence line (fence chars + info string + newline)
+            splits.push((block.header_start, block.header_end));
+
+            let code = &text[block.content_start..block.content_end];
+            let lang_part = block.lang.unwrap_or("");
+
+            if !lang_part.is_empty() && self.is_supported_language(lang_part) {
+                // Apply language-specific lexing
+                match self.lex_code(lang_part, code, block.content_start) {
+                    Ok(boundaries) => {
+                        let boundaries = if self.split_identifiers {
+                            identifier_split::split_identifier_spans(
+                                text,
+                                boundaries,
+                                &IdentifierSplitOptions {
+                                    split_on_digit_transitions: self.split_identifier_digits,
+                                    keep_whole_identifier: self.keep_whole_identifier_span,
+                                },
+                            )
+                        } else {
+                            boundaries
+                        };
+
+                        // Add lexed boundaries, recording any kind metadata
+                        for span in boundaries {
+                            splits.push((span.start, span.end));
+                            if let Some(kind) = span.kind {
+                                kinds.push((span.start, span.end, kind));
+                            }
+                        }
+                    }
+                    Err(_) => {
+                        // Fallback: add code as single block
+                        splits.push((block.content_start, block.content_end));
+                    }
+                }
+            } else {
+                // Unknown or unsupported language - add as single block
+                splits.push((block.content_start, block.content_end));
+            }
+
+            // Add closing fence line
+            splits.push((block.content_end, block.footer_end));
+
+            last_end = block.footer_end;
+        }
+
+        // Add remaining text
+        if last_end < text.len() {
+            splits.push((last_end, text.len()));
+        }
+
+        // If no code blocks found, return original
+        if splits.is_empty() {
+            splits.push((0, text.len()));
+        }
+
+        (splits, kinds)
+    }
+}
+
+impl PreTokenizer for CodeLexer {
+    fn pre_tokenize(&self, pretokenized: &mut PreTokenizedString) -> Result<()> {
+        pretokenized.split(|_idx, normalized| {
+            let text = normalized.get();
+            let (splits, _kinds) = self.compute_splits(text);
+
+            // Convert splits to normalized slices
+            Ok(splits
+                .into_iter()
+                .filter_map(|(start, end)| {
+                    if start < end {
+                        normalized.slice(Range::Original(start..end))
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OffsetReferential, OffsetType};
+
+    #[test]
+    fn is_sync() {
+        // `encode_batch` shares a single pre-tokenizer across threads, so
+        // `CodeLexer` must stay `Sync`. This is a compile-time check.
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<CodeLexer>();
+    }
+
+    #[test]
+    fn no_code_blocks() {
+        let text = "This is just plain text without code.";
+        let mut pretokenized = PreTokenizedString::from(text);
+        let lexer = CodeLexer::default();
+
+        lexer.pre_tokenize(&mut pretokenized).unwrap();
+
+        let splits = pretokenized.get_splits(OffsetReferential::Original, OffsetType::Byte);
+        assert_eq!(splits.len(), 1);
+        assert_eq!(splits[0].0, text);
+    }
+
+    #[test]
+    fn simple_code_block() {
+        let text = "```python\ndef test(): pass\n```";
+        let mut pretokenized = PreTokenizedString::from(text);
+        let lexer = CodeLexer::default();
+
+        lexer.pre_tokenize(&mut pretokenized).unwrap();
+
+        let splits = pretokenized.get_splits(OffsetReferential::Original, OffsetType::Byte);
+        // Should have at least: opening fence, code tokens, closing fence
+        assert!(splits.len() >= 3);
+    }
+
+    #[test]
+    fn serialization() {
+        let lexer = CodeLexer::default();
+        let lexer_s = r#"{"type":"CodeLexer","languages":["python","py"]}"#;
+
+        assert_eq!(serde_json::to_string(&lexer).unwrap(), lexer_s);
+        assert_eq!(serde_json::from_str::<CodeLexer>(lexer_s).unwrap(), lexer);
+    }
+
+    #[test]
+    fn mixed_content() {
+        let text = "Here is code:\n```python\nx = 1\n```\nMore text.";
+        let mut pretokenized = PreTokenizedString::from(text);
+        let lexer = CodeLexer::default();
+
+        lexer.pre_tokenize(&mut pretokenized).unwrap();
+
+        // Should split into: before, fence, code, fence, after
+        let splits = pretokenized.get_splits(OffsetReferential::Original, OffsetType::Byte);
+        assert!(splits.len() >= 4);
+    }
+
+    #[test]
+    fn tilde_fence_is_detected() {
+        let text = "~~~javascript\nlet x = 1\n~~~";
+        let mut pretokenized = PreTokenizedString::from(text);
+        let lexer = CodeLexer::new(vec!["javascript".to_string()]);
+
+        lexer.pre_tokenize(&mut pretokenized).unwrap();
+
+        let splits = pretokenized.get_splits(OffsetReferential::Original, OffsetType::Byte);
+        assert!(splits.len() >= 3);
+    }
+
+    #[test]
+    fn info_string_attributes_do_not_break_language_detection() {
+        let text = "```javascript title=\"x.js\"\nlet x = 1\n```";
+        let mut pretokenized = PreTokenizedString::from(text);
+        let lexer = CodeLexer::new(vec!["javascript".to_string()]);
+
+        lexer.pre_tokenize(&mut pretokenized).unwrap();
+
+        // The language should still be recognized as "javascript" (the rest
+        // of the info string is left alone rather than breaking detection),
+        // so the block gets lexed rather than falling back to one span:
+        // fence, `let`, `x`, `=`, `1`, fence.
+        let splits = pretokenized.get_splits(OffsetReferential::Original, OffsetType::Byte);
+        assert!(splits.len() >= 6);
+    }
+
+    #[test]
+    fn unregistered_language_uses_whitespace_fallback() {
+        let text = "```javascript\nlet x = 1\n```";
+        let mut pretokenized = PreTokenizedString::from(text);
+        let lexer = CodeLexer::new(vec!["javascript".to_string()]);
+
+        lexer.pre_tokenize(&mut pretokenized).unwrap();
+
+        let splits = pretokenized.get_splits(OffsetReferential::Original, OffsetType::Byte);
+        // fence, `let`, `x`, `=`, `1`, fence
+        assert!(splits.len() >= 6);
+    }
+
+    #[test]
+    fn custom_regex_lexer_backend_is_used() {
+        let mut groups = HashMap::new();
+        groups.insert(
+            "root".to_string(),
+            Group {
+                rules: vec![
+                    Rule {
+                        pattern: r"[A-Za-z_]+".to_string(),
+                        kind: Some(TokenKind::Identifier),
+                        emit: true,
+                        push: None,
+                        pop: false,
+                    },
+                    Rule {
+                        pattern: r"\s+".to_string(),
+                        kind: None,
+                        emit: false,
+                        push: None,
+                        pop: false,
+                    },
+                ],
+                inherits: None,
+            },
+        );
+
+        let mut lexer = CodeLexer::new(vec!["toml".to_string()]);
+        lexer.register_regex_backend("toml", RegexLexer::new(groups, "root"));
+
+        let text = "```toml\nkey value\n```";
+        let mut pretokenized = PreTokenizedString::from(text);
+        lexer.pre_tokenize(&mut pretokenized).unwrap();
+
+        let kinds = lexer.token_kinds(text);
+        assert_eq!(
+            kinds.iter().filter(|(_, _, k)| *k == TokenKind::Identifier).count(),
+            2
+        );
+    }
+
+    #[test]
+    fn custom_regex_lexer_backend_survives_serialization_round_trip() {
+        let mut groups = HashMap::new();
+        groups.insert(
+            "root".to_string(),
+            Group {
+                rules: vec![Rule {
+                    pattern: r"[A-Za-z_]+".to_string(),
+                    kind: Some(TokenKind::Identifier),
+                    emit: true,
+                    push: None,
+                    pop: false,
+                }],
+                inherits: None,
+            },
+        );
+
+        let mut lexer = CodeLexer::new(vec!["toml".to_string()]);
+        lexer.register_regex_backend("toml", RegexLexer::new(groups, "root"));
+
+        let json = serde_json::to_string(&lexer).unwrap();
+        let reloaded: CodeLexer = serde_json::from_str(&json).unwrap();
+        assert_eq!(reloaded, lexer);
+
+        let text = "```toml\nkey\n```";
+        let mut pretokenized = PreTokenizedString::from(text);
+        reloaded.pre_tokenize(&mut pretokenized).unwrap();
+
+        let kinds = reloaded.token_kinds(text);
+        assert!(kinds.iter().any(|(_, _, k)| *k == TokenKind::Identifier));
+    }
+
+    #[test]
+    fn splits_identifiers_when_enabled() {
+        let text = "```javascript\ngetUserName\n```";
+        let mut pretokenized = PreTokenizedString::from(text);
+        let lexer = CodeLexer::new(vec!["javascript".to_string()]).with_identifier_splitting(true);
+
+        lexer.pre_tokenize(&mut pretokenized).unwrap();
+
+        let splits = pretokenized.get_splits(OffsetReferential::Original, OffsetType::Byte);
+        let rendered: Vec<&str> = splits.iter().map(|(s, _)| *s).collect();
+        assert!(rendered.contains(&"get"));
+        assert!(rendered.contains(&"User"));
+        assert!(rendered.contains(&"Name"));
+        assert!(!rendered.contains(&"getUserName"));
+    }
+
+    #[test]
+    fn records_token_kind_metadata() {
+        let text = "```javascript\nlet 1\n```";
+        let mut pretokenized = PreTokenizedString::from(text);
+        let lexer = CodeLexer::new(vec!["javascript".to_string()]);
+
+        lexer.pre_tokenize(&mut pretokenized).unwrap();
+
+        let kinds = lexer.token_kinds(text);
+        assert!(kinds
+            .iter()
+            .any(|(_, _, kind)| *kind == TokenKind::Identifier));
+        assert!(kinds.iter().any(|(_, _, kind)| *kind == TokenKind::Number));
+    }
+}
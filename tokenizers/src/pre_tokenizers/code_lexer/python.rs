@@ -0,0 +1,204 @@
+use super::backend::{CodeLexerBackend, LexOptions, LexedSpan};
+use super::kind::TokenKind;
+use crate::tokenizer::Result;
+
+/// Lexes Python source using `rustpython_parser`'s lexer.
+#[derive(Debug, Clone, Default)]
+pub struct PythonBackend;
+
+#[cfg(feature = "python_lexer")]
+fn classify(token: &rustpython_parser::Tok) -> TokenKind {
+    use rustpython_parser::Tok;
+
+    match token {
+        Tok::Name { .. } => TokenKind::Identifier,
+        Tok::Int { .. } | Tok::Float { .. } | Tok::Complex { .. } => TokenKind::Number,
+        // F-strings aren't a separate variant - they're `Tok::String` with
+        // `kind: StringKind::FString`, which this arm already covers.
+        Tok::String { .. } => TokenKind::StringLiteral,
+        Tok::Comment { .. } => TokenKind::Comment,
+        Tok::Newline | Tok::Indent | Tok::Dedent => TokenKind::Whitespace,
+        Tok::False
+        | Tok::None
+        | Tok::True
+        | Tok::And
+        | Tok::As
+        | Tok::Assert
+        | Tok::Async
+        | Tok::Await
+        | Tok::Break
+        | Tok::Class
+        | Tok::Continue
+        | Tok::Def
+        | Tok::Del
+        | Tok::Elif
+        | Tok::Else
+        | Tok::Except
+        | Tok::Finally
+        | Tok::For
+        | Tok::From
+        | Tok::Global
+        | Tok::If
+        | Tok::Import
+        | Tok::In
+        | Tok::Is
+        | Tok::Lambda
+        | Tok::Nonlocal
+        | Tok::Not
+        | Tok::Or
+        | Tok::Pass
+        | Tok::Raise
+        | Tok::Return
+        | Tok::Try
+        | Tok::While
+        | Tok::With
+        | Tok::Yield => TokenKind::Keyword,
+        _ => TokenKind::Operator,
+    }
+}
+
+/// Lex `code` one resumable chunk at a time: on a lexical error, keep every
+/// good boundary emitted so far, emit the malformed region (from the end of
+/// the last good boundary up to the next newline, or end of input) as a
+/// single unclassified fallback span, then resume lexing the remainder
+/// starting right after that newline. This mirrors the resumable-lexing
+/// idea behind rust-analyzer's `ParsedToken`: a stream that keeps going
+/// instead of collapsing the whole block on the first error.
+///
+/// This loops over resync points rather than recursing into the remainder,
+/// so a block with many bad lines in a row (common in messy real-world
+/// markdown) doesn't grow one stack frame per error.
+#[cfg(feature = "python_lexer")]
+fn lex_with_recovery(
+    code: &str,
+    offset: usize,
+    options: &LexOptions,
+) -> Result<Vec<LexedSpan>> {
+    use rustpython_parser::lexer::lex;
+    use rustpython_parser::Mode;
+    use rustpython_parser::Tok;
+
+    let mut boundaries: Vec<LexedSpan> = Vec::new();
+    let mut remaining = code;
+    let mut base_offset = offset;
+
+    loop {
+        let mut resynced = false;
+
+        for token_result in lex(remaining, Mode::Module) {
+            match token_result {
+                Ok((token, range)) => {
+                    // Convert TextRange to byte offsets
+                    let start = range.start().to_usize() + base_offset;
+                    let end = range.end().to_usize() + base_offset;
+
+                    // Skip ENDMARKER token
+                    if matches!(token, Tok::EndOfFile) {
+                        continue;
+                    }
+
+                    // For newline tokens, attach to previous token instead of creating separate token
+                    if matches!(token, Tok::Newline) {
+                        if let Some(last_boundary) = boundaries.last_mut() {
+                            // Extend previous token's end by 1 to include the newline
+                            last_boundary.end += 1;
+                            // Don't add the newline as a separate boundary
+                            continue;
+                        }
+                    }
+
+                    let kind = classify(&token);
+
+                    if options.sub_lex_comments_and_strings
+                        && matches!(kind, TokenKind::StringLiteral | TokenKind::Comment)
+                    {
+                        use super::fallback::WhitespaceWordBackend;
+                        let inner = &code[(start - offset)..(end - offset)];
+                        let mut sub_spans =
+                            WhitespaceWordBackend.lex(inner, start, &LexOptions::default())?;
+                        // Re-tag sub-spans with the original string/comment kind so
+                        // callers can still tell "this was inside a string literal".
+                        for span in &mut sub_spans {
+                            span.kind = Some(kind);
+                        }
+                        boundaries.extend(sub_spans);
+                    } else {
+                        boundaries.push(LexedSpan::new(start, end, Some(kind)));
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Warning: Python lexing error, recovering at next line: {:?}", e);
+
+                    // Resync from the end of the last good boundary (relative
+                    // to `remaining`) up to the next newline, so the malformed
+                    // region is quarantined in one fallback span instead of
+                    // swallowing everything that lexed fine before it.
+                    let bad_start_rel = boundaries
+                        .last()
+                        .map(|span| span.end - base_offset)
+                        .unwrap_or(0);
+                    let resync_rel = remaining[bad_start_rel..]
+                        .find('\n')
+                        .map(|i| bad_start_rel + i + 1)
+                        .unwrap_or(remaining.len());
+
+                    if resync_rel > bad_start_rel {
+                        boundaries.push(LexedSpan::unclassified(
+                            base_offset + bad_start_rel,
+                            base_offset + resync_rel,
+                        ));
+                    }
+
+                    if resync_rel < remaining.len() {
+                        base_offset += resync_rel;
+                        remaining = &remaining[resync_rel..];
+                        resynced = true;
+                    }
+
+                    // The underlying iterator's state after an error isn't
+                    // reliable, so stop consuming it here; the outer loop
+                    // either resumes lexing the remainder or stops.
+                    break;
+                }
+            }
+        }
+
+        if !resynced {
+            break;
+        }
+    }
+
+    Ok(boundaries)
+}
+
+#[cfg(feature = "python_lexer")]
+impl CodeLexerBackend for PythonBackend {
+    fn lex(&self, code: &str, offset: usize, options: &LexOptions) -> Result<Vec<LexedSpan>> {
+        let boundaries = lex_with_recovery(code, offset, options)?;
+
+        // Fill in whitespace gaps by extending the next token backwards
+        let mut filled_boundaries = Vec::new();
+        let mut last_end = offset;
+
+        for span in boundaries {
+            // If there's a gap (whitespace), attach it to the current token by moving start backwards
+            let adjusted_start = if span.start > last_end {
+                last_end // Extend current token to include whitespace before it
+            } else {
+                span.start
+            };
+            last_end = span.end;
+            filled_boundaries.push(LexedSpan::new(adjusted_start, span.end, span.kind));
+        }
+
+        Ok(filled_boundaries)
+    }
+}
+
+#[cfg(not(feature = "python_lexer"))]
+impl CodeLexerBackend for PythonBackend {
+    fn lex(&self, code: &str, offset: usize, _options: &LexOptions) -> Result<Vec<LexedSpan>> {
+        eprintln!("Warning: Python lexer not available (feature 'python_lexer' not enabled)");
+        Ok(vec![LexedSpan::unclassified(offset, offset + code.len())])
+    }
+}
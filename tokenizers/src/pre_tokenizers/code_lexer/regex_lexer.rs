@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::backend::{CodeLexerBackend, LexOptions, LexedSpan};
+use super::kind::TokenKind;
+use crate::tokenizer::Result;
+use crate::utils::SysRegex;
+
+/// One rule within a [`Group`]: if `pattern` matches at the current
+/// position, optionally emit a token boundary and/or transition the group
+/// stack.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Rule {
+    /// Regex tried against the remaining input, anchored at the current
+    /// offset (i.e. it must match starting right there, not just anywhere
+    /// further ahead).
+    pub pattern: String,
+    /// Lexical classification to attach to the emitted span, if any.
+    #[serde(default)]
+    pub kind: Option<TokenKind>,
+    /// Whether this match should actually emit a span. `false` is useful
+    /// for rules that only exist to drive a group transition (e.g. opening
+    /// a string without wanting the quote itself as its own token).
+    #[serde(default = "default_emit")]
+    pub emit: bool,
+    /// Push this group onto the state stack after matching (e.g. entering
+    /// a string or a comment).
+    #[serde(default)]
+    pub push: Option<String>,
+    /// Pop the current group off the state stack after matching (e.g.
+    /// leaving a string or a comment).
+    #[serde(default)]
+    pub pop: bool,
+}
+
+fn default_emit() -> bool {
+    true
+}
+
+/// A named lexer state: an ordered list of rules, optionally inheriting
+/// from a parent group.
+///
+/// Matching within a group considers its own `rules` in definition order,
+/// followed by the parent's rules (via `inherits`). The longest match wins
+/// and ties go to the earlier rule, so a child group's rule overrides a
+/// parent's rule that matches the same length.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct Group {
+    pub rules: Vec<Rule>,
+    #[serde(default)]
+    pub inherits: Option<String>,
+}
+
+/// Data-driven, group/state-machine based lexer backend, in the spirit of
+/// Enso's flexer: lexers for languages the crate doesn't ship a native
+/// backend for can be defined entirely as data (e.g. loaded from JSON)
+/// instead of Rust code.
+///
+/// The lexer walks `code` keeping a current offset and a stack of active
+/// groups (starting with `start_group`). At each position, rules of the
+/// top-of-stack group (plus any inherited ones, tried after the group's
+/// own rules) are matched in order; the longest match wins, ties broken by
+/// definition order. A matching rule may emit a span and push/pop a group,
+/// modeling nested constructs like strings, comments, or interpolation. A
+/// position where nothing matches emits a single one-character span of
+/// kind [`TokenKind::Other`] and advances by one character, so the lexer
+/// never gets stuck.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub struct RegexLexer {
+    pub groups: HashMap<String, Group>,
+    pub start_group: String,
+}
+
+impl RegexLexer {
+    pub fn new(groups: HashMap<String, Group>, start_group: impl Into<String>) -> Self {
+        Self {
+            groups,
+            start_group: start_group.into(),
+        }
+    }
+
+    /// Flatten a group's own rules followed by its ancestors' rules
+    /// (inherited rules always come after a group's own, so children can
+    /// override parents). Guards against cyclic `inherits` chains.
+    fn resolve_rules(&self, group_name: &str) -> Vec<&Rule> {
+        let mut rules = Vec::new();
+        let mut visited = std::collections::HashSet::new();
+        let mut current = Some(group_name);
+
+        while let Some(name) = current {
+            if !visited.insert(name.to_string()) {
+                break; // cyclic inheritance, stop rather than loop forever
+            }
+            let Some(group) = self.groups.get(name) else {
+                break;
+            };
+            rules.extend(group.rules.iter());
+            current = group.inherits.as_deref();
+        }
+
+        rules
+    }
+}
+
+impl CodeLexerBackend for RegexLexer {
+    fn lex(&self, code: &str, offset: usize, _options: &LexOptions) -> Result<Vec<LexedSpan>> {
+        // Precompile every group's (own + inherited) rules once up front,
+        // instead of recompiling regexes at every position.
+        let mut compiled: HashMap<&str, Vec<(SysRegex, &Rule)>> = HashMap::new();
+        for name in self.groups.keys() {
+            let mut group_rules = Vec::new();
+            for rule in self.resolve_rules(name) {
+                if let Ok(regex) = SysRegex::new(&rule.pattern) {
+                    group_rules.push((regex, rule));
+                }
+            }
+            compiled.insert(name.as_str(), group_rules);
+        }
+
+        let mut spans = Vec::new();
+        let mut stack = vec![self.start_group.clone()];
+        let mut pos = 0usize;
+
+        while pos < code.len() {
+            let group_name = stack.last().expect("stack always has at least one group");
+            let rules = compiled.get(group_name.as_str());
+
+            let best = rules.and_then(|rules| {
+                rules
+                    .iter()
+                    .filter_map(|(regex, rule)| {
+                        let (start, end) = regex.find_iter(&code[pos..]).next()?;
+                        if start == 0 {
+                            Some((end, rule))
+                        } else {
+                            None
+                        }
+                    })
+                    // Longest match wins; ties keep the first rule in definition
+                    // order. `Iterator::max_by_key` returns the *last* maximal
+                    // element on a tie, which would prefer an inherited (parent)
+                    // rule over the child's own rule of equal length, so fold
+                    // manually and only replace the current best on a strict
+                    // improvement.
+                    .fold(None, |best: Option<(usize, &&Rule)>, candidate| match best {
+                        Some((best_len, _)) if best_len >= candidate.0 => best,
+                        _ => Some(candidate),
+                    })
+            });
+
+            match best {
+                Some((match_len, rule)) if match_len > 0 => {
+                    let start = pos;
+                    let end = pos + match_len;
+
+                    if rule.emit {
+                        spans.push(LexedSpan::new(offset + start, offset + end, rule.kind));
+                    }
+                    if let Some(push_group) = &rule.push {
+                        stack.push(push_group.clone());
+                    }
+                    if rule.pop && stack.len() > 1 {
+                        stack.pop();
+                    }
+
+                    pos = end;
+                }
+                _ => {
+                    // No rule matched here: emit a one-character error span
+                    // and keep going, so an unsupported construct doesn't
+                    // halt lexing of the rest of the block.
+                    let char_len = code[pos..].chars().next().map_or(1, char::len_utf8);
+                    spans.push(LexedSpan::new(
+                        offset + pos,
+                        offset + pos + char_len,
+                        Some(TokenKind::Other),
+                    ));
+                    pos += char_len;
+                }
+            }
+        }
+
+        Ok(spans)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word_number_lexer() -> RegexLexer {
+        let mut groups = HashMap::new();
+        groups.insert(
+            "root".to_string(),
+            Group {
+                rules: vec![
+                    Rule {
+                        pattern: r"[0-9]+".to_string(),
+                        kind: Some(TokenKind::Number),
+                        emit: true,
+                        push: None,
+                        pop: false,
+                    },
+                    Rule {
+                        pattern: r"[A-Za-z_]+".to_string(),
+                        kind: Some(TokenKind::Identifier),
+                        emit: true,
+                        push: None,
+                        pop: false,
+                    },
+                    Rule {
+                        pattern: r"\s+".to_string(),
+                        kind: Some(TokenKind::Whitespace),
+                        emit: false,
+                        push: None,
+                        pop: false,
+                    },
+                ],
+                inherits: None,
+            },
+        );
+        RegexLexer::new(groups, "root")
+    }
+
+    #[test]
+    fn emits_classified_spans_and_skips_whitespace() {
+        let lexer = word_number_lexer();
+        let spans = lexer.lex("foo 42 bar", 0, &LexOptions::default()).unwrap();
+
+        assert_eq!(
+            spans
+                .iter()
+                .map(|s| (&"foo 42 bar"[s.start..s.end], s.kind))
+                .collect::<Vec<_>>(),
+            vec![
+                ("foo", Some(TokenKind::Identifier)),
+                ("42", Some(TokenKind::Number)),
+                ("bar", Some(TokenKind::Identifier)),
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_groups_via_push_pop() {
+        let mut groups = HashMap::new();
+        groups.insert(
+            "root".to_string(),
+            Group {
+                rules: vec![Rule {
+                    pattern: "\"".to_string(),
+                    kind: None,
+                    emit: true,
+                    push: Some("string".to_string()),
+                    pop: false,
+                }],
+                inherits: None,
+            },
+        );
+        groups.insert(
+            "string".to_string(),
+            Group {
+                rules: vec![
+                    Rule {
+                        pattern: "\"".to_string(),
+                        kind: None,
+                        emit: true,
+                        push: None,
+                        pop: true,
+                    },
+                    Rule {
+                        pattern: "[^\"]+".to_string(),
+                        kind: Some(TokenKind::StringLiteral),
+                        emit: true,
+                        push: None,
+                        pop: false,
+                    },
+                ],
+                inherits: None,
+            },
+        );
+        let lexer = RegexLexer::new(groups, "root");
+
+        let text = "\"hi\"";
+        let spans = lexer.lex(text, 0, &LexOptions::default()).unwrap();
+        let rendered: Vec<&str> = spans.iter().map(|s| &text[s.start..s.end]).collect();
+        assert_eq!(rendered, vec!["\"", "hi", "\""]);
+    }
+}
@@ -0,0 +1,119 @@
+use super::backend::{CodeLexerBackend, LexOptions, LexedSpan};
+use super::kind::TokenKind;
+use crate::tokenizer::Result;
+
+/// Lexes Rust source by thinly bridging to `rustc_lexer`.
+///
+/// `rustc_lexer` only reports token *lengths*, not byte ranges or error
+/// information beyond `TokenKind::Unknown`/unterminated-literal flags, so
+/// this backend just walks the returned tokens and accumulates offsets.
+#[derive(Debug, Clone, Default)]
+pub struct RustBackend;
+
+#[cfg(feature = "rust_lexer")]
+fn classify(token_text: &str, kind: &rustc_lexer::TokenKind) -> TokenKind {
+    use rustc_lexer::{LiteralKind, TokenKind as RustcKind};
+
+    match kind {
+        RustcKind::Ident if is_rust_keyword(token_text) => TokenKind::Keyword,
+        RustcKind::Ident | RustcKind::RawIdent => TokenKind::Identifier,
+        RustcKind::Literal { kind: LiteralKind::Int { .. } | LiteralKind::Float { .. }, .. } => {
+            TokenKind::Number
+        }
+        RustcKind::Literal { .. } => TokenKind::StringLiteral,
+        RustcKind::LineComment | RustcKind::BlockComment { .. } => TokenKind::Comment,
+        RustcKind::Whitespace => TokenKind::Whitespace,
+        _ => TokenKind::Operator,
+    }
+}
+
+#[cfg(feature = "rust_lexer")]
+fn is_rust_keyword(word: &str) -> bool {
+    matches!(
+        word,
+        "as" | "break"
+            | "const"
+            | "continue"
+            | "crate"
+            | "else"
+            | "enum"
+            | "extern"
+            | "false"
+            | "fn"
+            | "for"
+            | "if"
+            | "impl"
+            | "in"
+            | "let"
+            | "loop"
+            | "match"
+            | "mod"
+            | "move"
+            | "mut"
+            | "pub"
+            | "ref"
+            | "return"
+            | "self"
+            | "Self"
+            | "static"
+            | "struct"
+            | "super"
+            | "trait"
+            | "true"
+            | "type"
+            | "unsafe"
+            | "use"
+            | "where"
+            | "while"
+            | "async"
+            | "await"
+            | "dyn"
+    )
+}
+
+#[cfg(feature = "rust_lexer")]
+impl CodeLexerBackend for RustBackend {
+    fn lex(&self, code: &str, offset: usize, options: &LexOptions) -> Result<Vec<LexedSpan>> {
+        let mut spans = Vec::new();
+        let mut pos = 0usize;
+
+        for token in rustc_lexer::tokenize(code) {
+            let start = pos;
+            let end = pos + token.len;
+            pos = end;
+
+            if matches!(token.kind, rustc_lexer::TokenKind::Whitespace) {
+                continue;
+            }
+
+            let kind = classify(&code[start..end], &token.kind);
+
+            if options.sub_lex_comments_and_strings
+                && matches!(kind, TokenKind::StringLiteral | TokenKind::Comment)
+            {
+                use super::fallback::WhitespaceWordBackend;
+                let mut sub_spans = WhitespaceWordBackend.lex(
+                    &code[start..end],
+                    offset + start,
+                    &LexOptions::default(),
+                )?;
+                for span in &mut sub_spans {
+                    span.kind = Some(kind);
+                }
+                spans.extend(sub_spans);
+            } else {
+                spans.push(LexedSpan::new(offset + start, offset + end, Some(kind)));
+            }
+        }
+
+        Ok(spans)
+    }
+}
+
+#[cfg(not(feature = "rust_lexer"))]
+impl CodeLexerBackend for RustBackend {
+    fn lex(&self, code: &str, offset: usize, _options: &LexOptions) -> Result<Vec<LexedSpan>> {
+        eprintln!("Warning: Rust lexer not available (feature 'rust_lexer' not enabled)");
+        Ok(vec![LexedSpan::unclassified(offset, offset + code.len())])
+    }
+}